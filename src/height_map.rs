@@ -0,0 +1,123 @@
+use crate::{Move, Position};
+
+#[derive(Debug, PartialEq)]
+pub struct HeightMap{
+    heights: Vec<Vec<u8>>,
+    start: Position,
+    end: Position
+}
+
+impl HeightMap{
+    /// Parses the heights, start ('S') and end ('E') positions out of the passed string in a
+    /// single pass, normalising each height to an elevation in `0..=25` ('a' is 0, 'z' is 25).
+    /// # Examples
+    /// ```
+    /// use advent_of_code_2022_12::HeightMap;
+    /// use advent_of_code_2022_12::Position;
+    ///
+    /// let height_map = HeightMap::new(concat!(
+    ///     "Sabqponm\n",
+    ///     "abcryxxl\n",
+    ///     "accszExk\n",
+    ///     "acctuvwj\n",
+    ///     "abdefghi"
+    /// ));
+    ///
+    /// assert_eq!(height_map.start(), Position{x: 0, y: 0});
+    /// assert_eq!(height_map.end(), Position{x: 5, y: 2});
+    /// assert_eq!(height_map.height_at(Position{x: 0, y: 0}), 0);
+    /// assert_eq!(height_map.height_at(height_map.end()), 25);
+    /// ```
+    pub fn new(map_str: &str) -> Self{
+        let mut start = None;
+        let mut end = None;
+
+        let heights = map_str
+            .lines()
+            .enumerate()
+            .map(|(y, line)| {
+                line
+                    .chars()
+                    .enumerate()
+                    .map(|(x, character)| match character{
+                        'S' => {start = Some(Position{x, y}); 0}
+                        'E' => {end = Some(Position{x, y}); 25}
+                        c if c.is_ascii_lowercase() => c as u8 - b'a',
+                        c => panic!("Invalid character {c}")
+                    })
+                    .collect::<Vec<u8>>()
+            })
+            .collect::<Vec<Vec<u8>>>();
+
+        HeightMap{
+            heights,
+            start: start.expect("map should contain a start position marked 'S'"),
+            end: end.expect("map should contain an end position marked 'E'")
+        }
+    }
+
+    pub fn width(&self) -> usize{
+        self.heights[0].len()
+    }
+
+    pub fn height(&self) -> usize{
+        self.heights.len()
+    }
+
+    pub fn start(&self) -> Position{
+        self.start
+    }
+
+    pub fn end(&self) -> Position{
+        self.end
+    }
+
+    pub fn height_at(&self, position: Position) -> u8{
+        self.heights[position.y][position.x]
+    }
+
+    /// The character ('a'..='z') that `height_at(position)` was normalised from.
+    pub fn char_at(&self, position: Position) -> char{
+        (self.height_at(position) + b'a') as char
+    }
+
+    /// The neighbours of `position` reachable by a single step that respects the climb rule:
+    /// a step may climb at most one elevation when `ascending`, or descend at most one
+    /// elevation otherwise.
+    pub fn neighbors(&self, position: Position, ascending: bool) -> impl Iterator<Item = Position> + '_{
+        let this_height = self.height_at(position);
+        let width = self.width();
+        let height = self.height();
+
+        [
+            (position.x + 1 < width).then(|| Position{x: position.x + 1, y: position.y}),
+            (position.x > 0).then(|| Position{x: position.x - 1, y: position.y}),
+            (position.y + 1 < height).then(|| Position{x: position.x, y: position.y + 1}),
+            (position.y > 0).then(|| Position{x: position.x, y: position.y - 1})
+        ]
+        .into_iter()
+        .flatten()
+        .filter(move |&next| {
+            let next_height = self.height_at(next);
+            if ascending{
+                this_height + 1 >= next_height
+            }else{
+                this_height.saturating_sub(1) <= next_height
+            }
+        })
+    }
+}
+
+impl Position{
+    pub(crate) fn direction_to(&self, other: &Position) -> Move{
+        if other.x > self.x{
+            Move::Right
+        }else if other.x < self.x{
+            Move::Left
+        }else if other.y > self.y{
+            Move::Down
+        }else{
+            Move::Up
+        }
+    }
+}
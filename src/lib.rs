@@ -1,5 +1,13 @@
 use std::collections::HashSet;
-use std::cmp::min;
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::collections::BinaryHeap;
+use std::cmp::Reverse;
+
+mod height_map;
+pub use height_map::HeightMap;
+
+pub mod render;
 
 #[derive(Debug, Eq, Hash, PartialEq, Clone, Copy)]
 pub enum Move{
@@ -9,16 +17,36 @@ pub enum Move{
     Right
 }
 
-#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
 pub struct Position{
     pub x: usize,
     pub y: usize
 }
 
+impl Position{
+    pub(crate) fn stepped(&self, dir: &Move) -> Position{
+        let mut next = *self;
+
+        match dir{
+            Move::Up   =>  next.y -= 1,
+            Move::Down =>  next.y += 1,
+            Move::Left =>  next.x -= 1,
+            Move::Right => next.x += 1
+        };
+
+        next
+    }
+
+    /// The Manhattan distance between this position and `other`
+    fn manhattan_distance(&self, other: &Position) -> usize{
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+}
+
 #[derive(Debug, PartialEq)]
 pub struct Surface{
     pub best_signal: Position,
-    pub heights: Vec<Vec<char>>
+    pub height_map: HeightMap
 }
 
 impl Surface{
@@ -28,63 +56,33 @@ impl Surface{
     /// use advent_of_code_2022_12::Surface;
     /// use advent_of_code_2022_12::Position;
     ///
-    /// assert_eq!(
-    ///     Surface{
-    ///         best_signal: Position{x: 5, y: 2},
-    ///         heights: vec![
-    ///             "aabqponm".chars().collect::<Vec<_>>(),
-    ///             "abcryxxl".chars().collect::<Vec<_>>(),
-    ///             "accszzxk".chars().collect::<Vec<_>>(),
-    ///             "acctuvwj".chars().collect::<Vec<_>>(),
-    ///             "abdefghi".chars().collect::<Vec<_>>()
-    ///         ]
-    ///     },
-    ///     Surface::new(concat!(
-    ///         "Sabqponm\n",
-    ///         "abcryxxl\n",
-    ///         "accszExk\n",
-    ///         "acctuvwj\n",
-    ///         "abdefghi"
-    /// )));
+    /// let surface = Surface::new(concat!(
+    ///     "Sabqponm\n",
+    ///     "abcryxxl\n",
+    ///     "accszExk\n",
+    ///     "acctuvwj\n",
+    ///     "abdefghi"
+    /// ));
+    ///
+    /// assert_eq!(surface.best_signal, Position{x: 5, y: 2});
+    /// assert_eq!(surface.height_map.height_at(Position{x: 0, y: 0}), 0);
+    /// assert_eq!(surface.height_map.height_at(surface.best_signal), 25);
     /// ```
     pub fn new(height_str: &str) -> Self{
-        let heights = height_str
-            .lines()
-            .map(|line| {
-                line
-                    .chars()
-                    .map(|character| {
-                        if character.is_ascii_lowercase(){
-                            character
-                        }else if character == 'S'{
-                            'a'
-                        }else if character == 'E'{
-                            'z'
-                        }else{
-                            panic!("Invalid character {character}");
-                        }
-                    })
-                    .collect::<Vec<char>>()
-            })
-            .collect::<Vec<Vec<char>>>();
-
-        let best_signal = Position{
-            x: height_str.lines().filter(|l| l.contains('E')).next().unwrap().find('E').unwrap(),
-            y: height_str.lines().enumerate().filter(|(_, l)| l.contains('E')).map(|(i,_)| i).next().unwrap()
-        };
+        let height_map = HeightMap::new(height_str);
 
         Surface{
-            best_signal,
-            heights
+            best_signal: height_map.end(),
+            height_map
         }
     }
 
     pub fn width(&self) -> usize{
-        self.heights[0].len()
+        self.height_map.width()
     }
 
     pub fn height(&self) -> usize{
-        self.heights.len()
+        self.height_map.height()
     }
 }
 
@@ -119,24 +117,22 @@ impl Player{
     /// )));
     /// ```
     pub fn new(map_str: &str) -> Self{
+        Self::at_start(&HeightMap::new(map_str))
+    }
+
+    /// Creates a player at the start position of an already-parsed `HeightMap`, so that a
+    /// `Surface`'s `HeightMap` can be shared with the `Player` that searches it instead of
+    /// reparsing the input.
+    pub fn at_start(height_map: &HeightMap) -> Self{
         Player{
-            position: Position{
-                x: map_str.lines().filter(|l| l.contains('S')).next().unwrap().find('S').unwrap(),
-                y: map_str.lines().enumerate().filter(|(_, l)| l.contains('S')).map(|(i,_)| i).next().unwrap()
-            },
+            position: height_map.start(),
             previous: HashSet::new()
         }
     }
 
     pub fn step(&mut self, dir: &Move){
         self.previous.insert(self.position);
-
-        match dir{
-            Move::Up   =>  self.position.y -= 1,
-            Move::Down =>  self.position.y += 1,
-            Move::Left =>  self.position.x -= 1,
-            Move::Right => self.position.x += 1
-        };
+        self.position = self.position.stepped(dir);
     }
 
     /// Returns a list of moves available to the player at its position
@@ -177,68 +173,84 @@ impl Player{
     /// );
     /// ```
     pub fn available_moves(&self, surface: &Surface, up: &bool) -> Vec<Move>{
-        let mut moves = Vec::new();
-        let this_height  = surface.heights[self.position.y][self.position.x];
+        let mut moves = surface.height_map
+            .neighbors(self.position, *up)
+            .map(|next| (self.position.direction_to(&next), surface.height_map.height_at(next)))
+            .collect::<Vec<_>>();
 
-        let height_check = |next_height: char| match up{
-            true  => this_height as u8 + 1 >= next_height as u8,
-            false => this_height as u8 - 1 <= next_height as u8
-        };
+        moves.sort_unstable_by_key(|(_m,h)| *h);
+        moves.iter().rev().map(|(m,_h)| *m).collect()
+    }
 
-        if self.position.x < surface.width() - 1{
-            let right_height = surface.heights[self.position.y][self.position.x+1];
-            if height_check(right_height){
-                moves.push((Move::Right, right_height));
-            }
-        }
-        if self.position.x > 0{
-            let left_height  = surface.heights[self.position.y][self.position.x-1];
-            if height_check(left_height){
-                moves.push((Move::Left, left_height));
-            }
-        }
-        if self.position.y < surface.height() - 1{
-            let down_height  = surface.heights[self.position.y+1][self.position.x];
-            if height_check(down_height){
-                moves.push((Move::Down, down_height));
+    /// Returns the length of the shortest path from this player's position satisfying
+    /// `end_condition`, found by breadth-first search over the grid graph whose edges are the
+    /// `HeightMap`'s neighbors.
+    fn shortest_path(&self, surface: &Surface, end_condition: &fn(&Position, &Surface) -> bool, up: &bool) -> usize{
+        let mut visited = (0..surface.height())
+            .map(|_| vec![false; surface.width()])
+            .collect::<Vec<_>>();
+        let mut frontier = VecDeque::new();
+
+        visited[self.position.y][self.position.x] = true;
+        frontier.push_back((self.position, 0_usize));
+
+        while let Some((position, dist)) = frontier.pop_front(){
+            if end_condition(&position, surface){
+                return dist;
             }
-        }
-        if self.position.y > 0{
-            let up_height    = surface.heights[self.position.y-1][self.position.x];
-            if height_check(up_height){
-                moves.push((Move::Up, up_height));
+
+            for next in surface.height_map.neighbors(position, *up){
+                if !visited[next.y][next.x]{
+                    visited[next.y][next.x] = true;
+                    frontier.push_back((next, dist + 1));
+                }
             }
         }
 
-        moves.sort_unstable_by_key(|(_m,h)| *h as u8);
-        return moves.iter().rev().map(|(m,_h)| *m).collect();
+        usize::MAX
     }
 
-    // Returns the length of the shortest path to the top of surface
-    fn shortest_path(&self, surface: &Surface, max_depth: &usize, distmap: &mut Vec<Vec<usize>>, end_condition: &fn(&Position, &Surface) -> bool, up: &bool) -> usize{
-        let mut new_max = *max_depth;
-        let mut paths = Vec::new();
-
-        if end_condition(&self.position, surface){
-            return self.previous.len()
-        }else if self.previous.contains(&self.position){
-            return usize::MAX;
-        }else if self.previous.len() >= new_max - 1{
-            return usize::MAX;
-        }else if distmap[self.position.y][self.position.x] <= self.previous.len(){
-            return usize::MAX;
-        }else{
-            distmap[self.position.y][self.position.x] = self.previous.len();
-
-            for m in self.available_moves(surface, &up).iter(){
-                let mut new_player = (*self).clone();
-                new_player.step(m);
-                paths.push(new_player.shortest_path(surface, &new_max, distmap, &end_condition, up));
-                new_max = min(*paths.last().unwrap(), new_max);
+    /// Same search as [`Player::shortest_path`], but recording a predecessor move for each
+    /// visited cell and walking that chain back from the goal to reconstruct the route, rather
+    /// than just its length. Returns `None` if `end_condition` is never satisfied, mirroring
+    /// `shortest_path`'s `usize::MAX` rather than conflating "no path" with a zero-length one.
+    fn trace_path(&self, surface: &Surface, end_condition: &fn(&Position, &Surface) -> bool, up: &bool) -> Option<Vec<Move>>{
+        let mut visited = (0..surface.height())
+            .map(|_| vec![false; surface.width()])
+            .collect::<Vec<_>>();
+        let mut predecessors = HashMap::new();
+        let mut frontier = VecDeque::new();
+
+        visited[self.position.y][self.position.x] = true;
+        frontier.push_back(self.position);
+
+        let mut goal = None;
+        while let Some(position) = frontier.pop_front(){
+            if end_condition(&position, surface){
+                goal = Some(position);
+                break;
+            }
+
+            for next in surface.height_map.neighbors(position, *up){
+                if !visited[next.y][next.x]{
+                    visited[next.y][next.x] = true;
+                    predecessors.insert(next, (position, position.direction_to(&next)));
+                    frontier.push_back(next);
+                }
             }
+        }
+
+        let mut path = Vec::new();
+        let mut current = goal?;
 
-            return *paths.iter().min().unwrap();
+        while current != self.position{
+            let (previous, m) = predecessors[&current];
+            path.push(m);
+            current = previous;
         }
+
+        path.reverse();
+        Some(path)
     }
 
     pub fn find_shortest_path_up(&self, surface: &Surface) -> usize{
@@ -246,31 +258,151 @@ impl Player{
 
         self.shortest_path(
             surface,
-            &usize::MAX,
-            &mut (0..surface.height())
-                .map(|_| (0..surface.width())
-                    .map(|_| usize::MAX)
-                    .collect::<Vec<_>>())
-                .collect::<Vec<_>>(),
+            &(at_top as fn(&Position, &Surface)->bool),
+            &true
+        )
+    }
+
+    /// Returns the moves along the shortest path to the top of surface, found the same way as
+    /// [`Player::find_shortest_path_up`] but recording a predecessor for each visited cell so
+    /// the route can be walked back from the goal. Returns `None` if the top is unreachable.
+    /// # Examples
+    /// ```
+    /// use advent_of_code_2022_12::Player;
+    /// use advent_of_code_2022_12::Surface;
+    ///
+    /// let mut player = Player::new(concat!(
+    ///     "Sabqponm\n",
+    ///     "abcryxxl\n",
+    ///     "accszExk\n",
+    ///     "acctuvwj\n",
+    ///     "abdefghi"
+    /// ));
+    /// let surface = Surface::new(concat!(
+    ///     "Sabqponm\n",
+    ///     "abcryxxl\n",
+    ///     "accszExk\n",
+    ///     "acctuvwj\n",
+    ///     "abdefghi"
+    /// ));
+    ///
+    /// let path = player.trace_shortest_path_up(&surface).unwrap();
+    /// assert_eq!(path.len(), 31);
+    ///
+    /// for m in &path{
+    ///     assert!(player.available_moves(&surface, &true).contains(m));
+    ///     player.step(m);
+    /// }
+    /// assert_eq!(player.position, surface.best_signal);
+    /// ```
+    pub fn trace_shortest_path_up(&self, surface: &Surface) -> Option<Vec<Move>>{
+        fn at_top(position: &Position, surface: &Surface) -> bool {*position == surface.best_signal}
+
+        self.trace_path(
+            surface,
             &(at_top as fn(&Position, &Surface)->bool),
             &true
         )
     }
 
     pub fn find_shortest_path_down(&self, surface: &Surface) -> usize{
-        fn at_bottom(position: &Position, surface: &Surface) -> bool{surface.heights[position.y][position.x] == 'a'}
+        fn at_bottom(position: &Position, surface: &Surface) -> bool{surface.height_map.height_at(*position) == 0}
+
         self.shortest_path(
             surface,
-            &usize::MAX,
-            &mut (0..surface.height())
-                .map(|_| (0..surface.width())
-                    .map(|_| usize::MAX)
-                    .collect::<Vec<_>>())
-                .collect::<Vec<_>>(),
             &(at_bottom as fn(&Position, &Surface)->bool),
             &false
         )
     }
+
+    /// Returns the moves along the shortest path to the bottom of surface, found the same way
+    /// as [`Player::find_shortest_path_down`] but recording a predecessor for each visited cell
+    /// so the route can be walked back from the goal. Returns `None` if the bottom is unreachable.
+    /// # Examples
+    /// ```
+    /// use advent_of_code_2022_12::Player;
+    /// use advent_of_code_2022_12::Surface;
+    /// use std::collections::HashSet;
+    ///
+    /// let surface = Surface::new(concat!(
+    ///     "Sabqponm\n",
+    ///     "abcryxxl\n",
+    ///     "accszExk\n",
+    ///     "acctuvwj\n",
+    ///     "abdefghi"
+    /// ));
+    /// let mut player = Player{position: surface.best_signal, previous: HashSet::new()};
+    ///
+    /// let path = player.trace_shortest_path_down(&surface).unwrap();
+    /// assert_eq!(path.len(), 29);
+    ///
+    /// for m in &path{
+    ///     assert!(player.available_moves(&surface, &false).contains(m));
+    ///     player.step(m);
+    /// }
+    /// assert_eq!(surface.height_map.height_at(player.position), 0);
+    /// ```
+    pub fn trace_shortest_path_down(&self, surface: &Surface) -> Option<Vec<Move>>{
+        fn at_bottom(position: &Position, surface: &Surface) -> bool{surface.height_map.height_at(*position) == 0}
+
+        self.trace_path(
+            surface,
+            &(at_bottom as fn(&Position, &Surface)->bool),
+            &false
+        )
+    }
+
+    /// Returns the length of the shortest path to the top of surface, found by A* search using
+    /// the Manhattan distance to `best_signal` as an (admissible, since every move changes one
+    /// coordinate by one) heuristic.
+    /// # Examples
+    /// ```
+    /// use advent_of_code_2022_12::Player;
+    /// use advent_of_code_2022_12::Surface;
+    ///
+    /// let player = Player::new(concat!(
+    ///     "Sabqponm\n",
+    ///     "abcryxxl\n",
+    ///     "accszExk\n",
+    ///     "acctuvwj\n",
+    ///     "abdefghi"
+    /// ));
+    /// let surface = Surface::new(concat!(
+    ///     "Sabqponm\n",
+    ///     "abcryxxl\n",
+    ///     "accszExk\n",
+    ///     "acctuvwj\n",
+    ///     "abdefghi"
+    /// ));
+    ///
+    /// assert_eq!(31, player.find_shortest_path_astar(&surface));
+    /// ```
+    pub fn find_shortest_path_astar(&self, surface: &Surface) -> usize{
+        let mut best_g = HashMap::new();
+        let mut frontier = BinaryHeap::new();
+
+        best_g.insert(self.position, 0_usize);
+        frontier.push(Reverse((self.position.manhattan_distance(&surface.best_signal), 0_usize, self.position)));
+
+        while let Some(Reverse((_f, g, position))) = frontier.pop(){
+            if g > *best_g.get(&position).unwrap_or(&usize::MAX){
+                continue;
+            }else if position == surface.best_signal{
+                return g;
+            }
+
+            for next in surface.height_map.neighbors(position, true){
+                let tentative_g = g + 1;
+
+                if tentative_g < *best_g.get(&next).unwrap_or(&usize::MAX){
+                    best_g.insert(next, tentative_g);
+                    frontier.push(Reverse((tentative_g + next.manhattan_distance(&surface.best_signal), tentative_g, next)));
+                }
+            }
+        }
+
+        usize::MAX
+    }
 }
 
 /// Finds the shortest path to the top and returns its length
@@ -289,8 +421,8 @@ impl Player{
 /// )));
 /// ```
 pub fn shortest_path_up(input: &str) -> usize{
-    let player = Player::new(input);
     let surface = Surface::new(input);
+    let player = Player::at_start(&surface.height_map);
 
     return player.find_shortest_path_up(&surface);
 }
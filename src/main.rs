@@ -2,12 +2,25 @@ use std::env;
 use std::fs;
 use advent_of_code_2022_12::shortest_path_up;
 use advent_of_code_2022_12::shortest_path_down;
+use advent_of_code_2022_12::{Player, Position, Surface};
 
 fn main() {
-    let args = env::args().collect::<Vec<_>>();
-    let file_path = &args[1];
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    let visualize = args.iter().any(|arg| arg == "--visualize");
+    let file_path = args.iter().find(|arg| *arg != "--visualize").expect("a puzzle input file path is required");
     let contents = fs::read_to_string(file_path).expect("Should have been able to read {file_path}");
 
+    if visualize{
+        fn at_top(position: &Position, surface: &Surface) -> bool {*position == surface.best_signal}
+
+        let surface = Surface::new(&contents);
+        let player = Player::at_start(&surface.height_map);
+
+        surface
+            .animate_search(&player, at_top, true)
+            .expect("Should have been able to render to the terminal");
+    }
+
     println!("The shortest path up is {} steps long", shortest_path_up(&contents));
     println!("The shortest path down is {} steps long", shortest_path_down(&contents));
 }
@@ -0,0 +1,107 @@
+//! Optional terminal visualization of a search over a [`Surface`], drawn with `termion` and
+//! coloured by height with `colorous`.
+
+use crate::{Player, Position, Surface};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::io::{self, Write};
+use std::thread::sleep;
+use std::time::Duration;
+
+const FRAME_DELAY: Duration = Duration::from_millis(20);
+
+impl Surface{
+    /// Draws the surface to the terminal, colouring each cell by height on the
+    /// [`colorous::VIRIDIS`] gradient, dimming `visited` cells, and highlighting `path`.
+    pub fn render(&self, visited: &[Position], path: &[Position]) -> io::Result<()>{
+        let visited = visited.iter().collect::<HashSet<_>>();
+        let path = path.iter().collect::<HashSet<_>>();
+        let mut out = io::stdout();
+
+        write!(out, "{}{}", termion::clear::All, termion::cursor::Goto(1, 1))?;
+
+        for y in 0..self.height(){
+            for x in 0..self.width(){
+                let position = Position{x, y};
+                let t = self.height_map.height_at(position) as f64 / 25.0;
+                let color = colorous::VIRIDIS.eval_continuous(t);
+
+                let glyph = if path.contains(&position){
+                    '*'
+                }else if visited.contains(&position){
+                    'o'
+                }else{
+                    self.height_map.char_at(position)
+                };
+
+                write!(
+                    out,
+                    "{}{}{}",
+                    termion::color::Bg(termion::color::Rgb(color.r, color.g, color.b)),
+                    termion::color::Fg(termion::color::Black),
+                    glyph
+                )?;
+            }
+            write!(out, "{}\r\n", termion::color::Fg(termion::color::Reset))?;
+        }
+
+        write!(out, "{}", termion::color::Bg(termion::color::Reset))?;
+        out.flush()
+    }
+
+    /// Animates the breadth-first search `player` would run to satisfy `end_condition`,
+    /// repainting the frontier as it grows and finally highlighting the discovered path.
+    pub fn animate_search(&self, player: &Player, end_condition: fn(&Position, &Surface) -> bool, up: bool) -> io::Result<()>{
+        let mut visited_set = (0..self.height())
+            .map(|_| vec![false; self.width()])
+            .collect::<Vec<_>>();
+        let mut visited_order = Vec::new();
+        let mut predecessors = HashMap::new();
+        let mut frontier = VecDeque::new();
+
+        visited_set[player.position.y][player.position.x] = true;
+        visited_order.push(player.position);
+        frontier.push_back(player.position);
+
+        let mut goal = None;
+        while let Some(position) = frontier.pop_front(){
+            self.render(&visited_order, &[])?;
+            sleep(FRAME_DELAY);
+
+            if end_condition(&position, self){
+                goal = Some(position);
+                break;
+            }
+
+            for next in self.height_map.neighbors(position, up){
+                if !visited_set[next.y][next.x]{
+                    visited_set[next.y][next.x] = true;
+                    predecessors.insert(next, position);
+                    visited_order.push(next);
+                    frontier.push_back(next);
+                }
+            }
+        }
+
+        let path = match goal{
+            Some(position) => trace_back(position, player.position, &predecessors),
+            None => Vec::new()
+        };
+
+        self.render(&visited_order, &path)
+    }
+}
+
+fn trace_back(goal: Position, start: Position, predecessors: &HashMap<Position, Position>) -> Vec<Position>{
+    let mut path = Vec::new();
+    let mut current = goal;
+
+    while current != start{
+        path.push(current);
+        current = predecessors[&current];
+    }
+    path.push(start);
+
+    path.reverse();
+    path
+}
+